@@ -0,0 +1,31 @@
+// n8n-clone - Command-Line Interface
+// Defines the subcommands the binary supports. `main` used to hard-code a
+// single serve path; this gives it `serve` (the old default behavior),
+// `migrate`, and `verify-config` for CI/deploy use without spinning up
+// the full HTTP server.
+
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "n8n-clone-server")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+
+    /// Path to a `key = value` config file, consulted after env vars and
+    /// before built-in defaults.
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Bind and run the HTTP server (the previous default behavior).
+    Serve,
+    /// Run pending database migrations, then exit.
+    Migrate,
+    /// Load and validate configuration and DB connectivity, then exit
+    /// 0 (ok) or 1 (invalid) without starting the HTTP server.
+    VerifyConfig,
+}
@@ -0,0 +1,70 @@
+// n8n-clone - Layered Configuration Loader
+// Settings are resolved in priority order: an app-prefixed environment
+// variable first, then a value from the config file passed on the CLI
+// (if any), then a hard-coded default. This keeps `serve`/`migrate`/
+// `verify-config` all reading configuration the same way.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+const ENV_PREFIX: &str = "N8N_CLONE";
+const DEFAULT_BIND_ADDR: &str = "127.0.0.1:5678";
+const DEFAULT_DATABASE_URL: &str = "postgres://user@host/n8n";
+
+/// Resolved application configuration, shared by every CLI subcommand.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// `host:port` string passed straight to `HttpServer::bind`.
+    pub bind_addr: String,
+    pub database_url: String,
+}
+
+impl Config {
+    /// Loads configuration, preferring `N8N_CLONE_*` env vars, falling back
+    /// to the `key = value` pairs in `config_file` (if provided and
+    /// readable), and finally to built-in defaults.
+    pub fn load(config_file: Option<&Path>) -> Self {
+        let file_values = config_file
+            .map(read_config_file)
+            .unwrap_or_default();
+
+        Config {
+            bind_addr: resolve("BIND_ADDR", &file_values, DEFAULT_BIND_ADDR),
+            database_url: resolve("DATABASE_URL", &file_values, DEFAULT_DATABASE_URL),
+        }
+    }
+}
+
+/// Resolves a single setting: env var `{ENV_PREFIX}_{key}`, then the config
+/// file's `key`, then `default`.
+fn resolve(key: &str, file_values: &HashMap<String, String>, default: &str) -> String {
+    std::env::var(format!("{}_{}", ENV_PREFIX, key))
+        .ok()
+        .or_else(|| file_values.get(key).cloned())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// Reads a simple `key = value` config file, one setting per line, ignoring
+/// blank lines and `#` comments. Missing or unreadable files resolve to an
+/// empty map so callers fall through to env vars / defaults.
+fn read_config_file(path: &Path) -> HashMap<String, String> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::warn!("could not read config file {}: {}", path.display(), e);
+            return HashMap::new();
+        }
+    };
+
+    contents
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.split_once('=')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
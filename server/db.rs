@@ -0,0 +1,86 @@
+// n8n-clone - Database Service
+// Wraps a real sqlx Postgres connection pool. Replaces the old sleep-and-
+// print mock with an actual pool plus slow-statement logging, shared
+// across handlers the same way `WorkflowEngine` is (an `Arc` in `web::Data`).
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{ConnectOptions, Pool, Postgres};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Default slow-query threshold, overridable via `DB_SLOW_QUERY_MS`.
+const DEFAULT_SLOW_QUERY_MS: u64 = 1000;
+
+/// A single workflow row as persisted in the `workflows` table.
+#[derive(Debug, sqlx::FromRow, serde::Serialize)]
+pub struct WorkflowRow {
+    pub id: String,
+    pub name: String,
+}
+
+/// Owns the Postgres connection pool. Constructed once at startup and
+/// shared via `Arc` + `web::Data` alongside `WorkflowEngine`.
+pub struct DatabaseService {
+    pool: Pool<Postgres>,
+}
+
+impl DatabaseService {
+    /// Builds `PgConnectOptions` from `database_url`, wires up slow-statement
+    /// logging at the configured threshold (`DB_SLOW_QUERY_MS`, default
+    /// 1000ms) with all statements logged at TRACE, and opens the pool.
+    /// Fails fast with the same error path `connect()` used to: the caller
+    /// in `main` exits the process on `Err`.
+    pub async fn connect(database_url: &str) -> Result<Self, String> {
+        let connect_options = Self::connect_options(database_url)?;
+        let pool = PgPoolOptions::new()
+            .connect_with(connect_options)
+            .await
+            .map_err(|e| format!("failed to connect to database: {}", e))?;
+
+        println!("  [Init] Database connection pool established.");
+        Ok(DatabaseService { pool })
+    }
+
+    /// Builds the pool the same way `connect` does, but without eagerly
+    /// opening the TCP connection - a bad or unreachable `database_url`
+    /// only surfaces once a query actually runs, instead of at construction
+    /// time. Used by the integration test harness so `spawn_app` doesn't
+    /// require a reachable Postgres for tests that never touch the DB; not
+    /// used on the `serve`/`migrate`/`verify-config` paths, which keep
+    /// `connect`'s fail-fast behavior.
+    pub fn connect_lazy(database_url: &str) -> Result<Self, String> {
+        let connect_options = Self::connect_options(database_url)?;
+        let pool = PgPoolOptions::new().connect_lazy_with(connect_options);
+        Ok(DatabaseService { pool })
+    }
+
+    fn connect_options(database_url: &str) -> Result<PgConnectOptions, String> {
+        let slow_query_threshold = std::env::var("DB_SLOW_QUERY_MS")
+            .ok()
+            .and_then(|v| v.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or_else(|| Duration::from_millis(DEFAULT_SLOW_QUERY_MS));
+
+        let connect_options = PgConnectOptions::from_str(database_url)
+            .map_err(|e| format!("invalid DATABASE_URL: {}", e))?;
+        Ok(connect_options
+            .log_statements(log::LevelFilter::Trace)
+            .log_slow_statements(log::LevelFilter::Warn, slow_query_threshold))
+    }
+
+    /// Runs any pending migrations under `./migrations` against the pool.
+    pub async fn run_migrations(&self) -> Result<(), String> {
+        sqlx::migrate!("./migrations")
+            .run(&self.pool)
+            .await
+            .map_err(|e| format!("failed to run migrations: {}", e))
+    }
+
+    /// Returns the full workflow list backing `GET /api/v1/workflows`.
+    pub async fn list_workflows(&self) -> Result<Vec<WorkflowRow>, String> {
+        sqlx::query_as::<_, WorkflowRow>("SELECT id, name FROM workflows ORDER BY name")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("failed to list workflows: {}", e))
+    }
+}
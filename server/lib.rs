@@ -0,0 +1,403 @@
+// n8n-clone - Application Library (Bogus/Mock Implementation)
+// Owns the `App` builder, service initialization, and route wiring so both
+// the real binary and the integration test harness can stand up the same
+// server. `main` (in `boot.rs`) is now a thin wrapper around `run()`.
+
+pub mod cli;
+pub mod config;
+pub mod db;
+pub mod payload;
+pub mod queue;
+pub mod telemetry;
+pub mod ws;
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{Server, ServiceFactory, ServiceRequest, ServiceResponse};
+use actix_web::{web, App, Error, HttpRequest, HttpResponse, HttpServer, Responder};
+use actix_web_actors::ws as actix_ws;
+use config::Config;
+use db::DatabaseService;
+use payload::NodeInput;
+use queue::{JobQueue, JobState, RunnerMessage};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use ws::{ExecutionEvent, ExecutionHub, ExecutionSocket};
+
+// --- Mock Service Definitions (These structs would hold the actual logic) ---
+
+/// Mock structure for the Node Registry
+struct NodeRegistry;
+impl NodeRegistry {
+    fn new() -> Self {
+        println!("  [Init] NodeRegistry created.");
+        NodeRegistry {}
+    }
+    fn register_default_nodes(&self) {
+        println!("  [Init] Registered 42 default nodes.");
+    }
+}
+
+/// Workflow Engine: owns the pending-execution queue that remote runner
+/// processes poll for work. Executions no longer run inline on the web
+/// thread; `trigger_workflow` only enqueues and returns the new exec id.
+struct WorkflowEngine {
+    is_running: Mutex<bool>,
+    queue: Arc<JobQueue>,
+    events: Arc<ExecutionHub>,
+}
+
+impl WorkflowEngine {
+    fn new(queue: Arc<JobQueue>, events: Arc<ExecutionHub>) -> Self {
+        println!("  [Init] WorkflowEngine created.");
+        WorkflowEngine { is_running: Mutex::new(false), queue, events }
+    }
+    fn start_worker(&self) {
+        let mut running = self.is_running.lock().unwrap();
+        *running = true;
+        println!("  [Init] Workflow execution worker started.");
+    }
+    /// Enqueues a pending execution for a remote runner to claim, using an
+    /// exec id the caller already reserved (so uploaded files decoded
+    /// ahead of this call can be staged under the same id's artifacts
+    /// directory). The actual execution happens out-of-process once a
+    /// runner polls it via `POST /internal/runner/poll`; `/ws/executions/{id}`
+    /// only gets events once that runner reports `TaskProgress`/`JobResult`
+    /// back through the same endpoint - there is no simulated completion.
+    async fn trigger_workflow(&self, id: &str, exec_id: String, input: NodeInput) -> Result<String, String> {
+        let payload = serde_json::to_vec(&input).map_err(|e| format!("could not serialize node input: {}", e))?;
+        tracing::info!(workflow_id = %id, "enqueuing workflow execution, payload length {}", payload.len());
+        self.queue.enqueue_with_id(exec_id.clone(), id, payload);
+        tracing::Span::current().record("execution_id", &exec_id.as_str());
+
+        Ok(exec_id)
+    }
+}
+
+/// The full set of shared services a running server needs, bundled so
+/// `build_app` only has to take one argument and both `run()` and the
+/// integration test harness construct it the same way.
+#[derive(Clone)]
+pub struct AppState {
+    workflow_engine: Arc<WorkflowEngine>,
+    execution_hub: Arc<ExecutionHub>,
+    db_service: Arc<DatabaseService>,
+    job_queue: Arc<JobQueue>,
+}
+
+impl AppState {
+    /// Initializes every service and connects to the database, failing
+    /// fast (returning `Err`) on pool acquisition errors exactly as the
+    /// original inline `connect()` error path did.
+    pub async fn init(cfg: &Config) -> Result<Self, String> {
+        let db_service = DatabaseService::connect(&cfg.database_url).await?;
+        Ok(Self::with_db_service(db_service))
+    }
+
+    /// Builds the rest of the services around an already-constructed
+    /// `DatabaseService`, so callers that need a non-default connection
+    /// strategy (the test harness's lazily-connected pool, via
+    /// `build_server_for_tests`) don't have to duplicate this wiring.
+    fn with_db_service(db_service: DatabaseService) -> Self {
+        let node_registry = NodeRegistry::new();
+        node_registry.register_default_nodes();
+
+        let job_queue = Arc::new(JobQueue::new("./artifacts"));
+        job_queue.start_reaper();
+        let execution_hub = Arc::new(ExecutionHub::new());
+        let workflow_engine = Arc::new(WorkflowEngine::new(Arc::clone(&job_queue), Arc::clone(&execution_hub)));
+        workflow_engine.start_worker();
+
+        AppState { workflow_engine, execution_hub, db_service: Arc::new(db_service), job_queue }
+    }
+}
+
+// --- Handlers (Actix Web request processing functions) ---
+
+/// Handler for the core API status check (GET /api/v1/status)
+async fn status_check() -> impl Responder {
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "ok",
+        "service": "n8n-clone-rust-backend",
+        "version": "0.1.0-bogus"
+    }))
+}
+
+/// Handler for listing persisted workflows (GET /api/v1/workflows)
+async fn list_workflows(db: web::Data<Arc<DatabaseService>>) -> impl Responder {
+    match db.list_workflows().await {
+        Ok(rows) => HttpResponse::Ok().json(rows),
+        Err(e) => HttpResponse::InternalServerError().json(serde_json::json!({
+            "message": "Error listing workflows",
+            "error": e
+        })),
+    }
+}
+
+/// Handler for the public webhook listener (POST /webhook/{workflowId}).
+/// The body is decoded per its `Content-Type` (JSON, protobuf, or
+/// multipart form data) into a normalized `NodeInput` before the engine
+/// ever sees it.
+async fn webhook_trigger(
+    req: HttpRequest,
+    path: web::Path<String>,    // Extracts the workflow ID from the URL path
+    payload: web::Payload,      // Raw request body stream
+    engine_data: web::Data<Arc<WorkflowEngine>>, // Shared application state
+    queue_data: web::Data<Arc<JobQueue>>,
+) -> impl Responder {
+    let workflow_id = path.into_inner();
+    tracing::info!(workflow_id = %workflow_id, "incoming webhook");
+
+    let exec_id = queue_data.new_exec_id();
+    let artifacts_dir = queue_data.artifacts_dir(&exec_id);
+    let node_input = match payload::decode(&req, payload, &artifacts_dir).await {
+        Ok(input) => input,
+        Err(payload::DecodeError::UnsupportedContentType(reason)) => {
+            return HttpResponse::UnsupportedMediaType().json(serde_json::json!({
+                "message": "Error triggering workflow",
+                "error": reason
+            }));
+        }
+        Err(payload::DecodeError::PayloadTooLarge(reason)) => {
+            return HttpResponse::PayloadTooLarge().json(serde_json::json!({
+                "message": "Error triggering workflow",
+                "error": reason
+            }));
+        }
+    };
+
+    match engine_data.trigger_workflow(&workflow_id, exec_id, node_input).await {
+        Ok(execution_id) => {
+            tracing::info!(execution_id = %execution_id, "workflow triggered successfully");
+            HttpResponse::Ok().json(serde_json::json!({
+                "message": "Workflow triggered successfully",
+                "executionId": execution_id
+            }))
+        },
+        Err(e) => {
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "message": "Error triggering workflow",
+                "error": e
+            }))
+        }
+    }
+}
+
+/// Handler for the runner-facing endpoint (POST /internal/runner/poll). A
+/// remote runner process speaks `RunnerMessage` here for its whole
+/// lifecycle, not just to ask for work:
+/// - `RequestJob` long-polls for the next pending execution, handed back
+///   immediately if one is already queued, otherwise the connection is
+///   held open for a short window to avoid runners hammering the server.
+/// - `TaskProgress` renews the job's lease (see `JobQueue::record_progress`)
+///   and republishes the step as an `ExecutionEvent` for WS viewers.
+/// - `JobResult` marks the job complete, removing it from the in-flight
+///   table, and publishes the terminal `ExecutionEvent`.
+async fn runner_poll(
+    msg: web::Json<RunnerMessage>,
+    engine_data: web::Data<Arc<WorkflowEngine>>,
+) -> impl Responder {
+    match msg.into_inner() {
+        RunnerMessage::RequestJob => {
+            const POLL_TIMEOUT: Duration = Duration::from_secs(25);
+            const RETRY_INTERVAL: Duration = Duration::from_millis(250);
+
+            let deadline = tokio::time::Instant::now() + POLL_TIMEOUT;
+            loop {
+                if let Some(job) = engine_data.queue.claim_next() {
+                    let job_info = RunnerMessage::JobInfo {
+                        exec_id: job.exec_id,
+                        workflow_id: job.workflow_id,
+                        payload: serde_json::Value::String(String::from_utf8_lossy(&job.payload).to_string()),
+                    };
+                    return HttpResponse::Ok().json(job_info);
+                }
+
+                if tokio::time::Instant::now() >= deadline {
+                    return HttpResponse::Ok().json(serde_json::json!({ "type": "NoJob" }));
+                }
+
+                tokio::time::sleep(RETRY_INTERVAL).await;
+            }
+        }
+
+        RunnerMessage::TaskProgress { exec_id, step, status } => {
+            // A step failing doesn't end the execution - only a `JobResult`
+            // does that - so this never emits the terminal
+            // `ExecutionEvent::ExecutionFailed`, and the job stays in
+            // `record_progress`'s lease rather than `complete`d.
+            engine_data.queue.record_progress(&exec_id);
+            let event = match status {
+                JobState::Running => ExecutionEvent::NodeStarted { node_id: step },
+                JobState::Success => {
+                    ExecutionEvent::NodeFinished { node_id: step, output_summary: "ok".to_string() }
+                }
+                JobState::Failed => ExecutionEvent::NodeFailed { node_id: step, error: "step failed".to_string() },
+            };
+            engine_data.events.publish(&exec_id, event);
+            HttpResponse::Ok().json(serde_json::json!({ "type": "Ack" }))
+        }
+
+        RunnerMessage::JobResult { exec_id, state } => {
+            engine_data.queue.complete(&exec_id);
+            let event = match state {
+                JobState::Success => ExecutionEvent::ExecutionCompleted,
+                JobState::Failed | JobState::Running => {
+                    ExecutionEvent::ExecutionFailed { error: "execution did not complete successfully".to_string() }
+                }
+            };
+            engine_data.events.publish(&exec_id, event);
+            HttpResponse::Ok().json(serde_json::json!({ "type": "Ack" }))
+        }
+
+        RunnerMessage::JobInfo { .. } => HttpResponse::BadRequest().json(serde_json::json!({
+            "message": "unexpected message",
+            "error": "JobInfo is sent by the server, not a runner"
+        })),
+    }
+}
+
+/// Handler for live execution streaming (GET /ws/executions/{executionId}).
+/// Upgrades the connection to a WebSocket and attaches an `ExecutionSocket`
+/// actor subscribed to the requested execution's event channel.
+async fn execution_ws(
+    req: HttpRequest,
+    stream: web::Payload,
+    path: web::Path<String>,
+    hub_data: web::Data<Arc<ExecutionHub>>,
+) -> actix_web::Result<HttpResponse> {
+    let execution_id = path.into_inner();
+    actix_ws::start(ExecutionSocket::new(execution_id, hub_data.get_ref().clone()), &req, stream)
+}
+
+// --- App Factory ---
+
+/// Builds the `App` with every route and shared service wired in. Used by
+/// both `run()` for the real server and the integration test harness, so
+/// handlers are exercised identically in tests and production.
+pub fn build_app(
+    state: AppState,
+) -> App<
+    impl ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<impl MessageBody>,
+        Error = Error,
+        InitError = (),
+    >,
+> {
+    App::new()
+        // State: Share the engine and the live-execution hub globally across all handlers
+        .app_data(web::Data::new(Arc::clone(&state.workflow_engine)))
+        .app_data(web::Data::new(Arc::clone(&state.execution_hub)))
+        .app_data(web::Data::new(Arc::clone(&state.db_service)))
+        .app_data(web::Data::new(Arc::clone(&state.job_queue)))
+
+        // Middleware: open a tracing span (method, path, request id) per request
+        .wrap(telemetry::RequestTracing)
+
+        // --- Define API Routes ---
+        .service(
+            web::scope("/api/v1")
+                .route("/status", web::get().to(status_check))
+                .route("/workflows", web::get().to(list_workflows))
+        )
+
+        // --- Define Webhook Listener ---
+        // Actix allows path variables like {workflowId}
+        .service(
+            web::scope("/webhook")
+                .route("/{workflowId}", web::post().to(webhook_trigger))
+        )
+
+        // --- Runner-facing endpoints (not exposed publicly) ---
+        .service(
+            web::scope("/internal/runner")
+                .route("/poll", web::post().to(runner_poll))
+        )
+
+        // --- Live Execution Streaming ---
+        .service(
+            web::scope("/ws")
+                .route("/executions/{executionId}", web::get().to(execution_ws))
+        )
+
+        // --- Mock Serving the UI (Requires a static files service) ---
+        // In a real setup, we would serve static files from a 'dist' directory.
+        // .service(actix_files::Files::new("/", "./dist").index_file("index.html"))
+}
+
+/// Initializes services, binds `cfg.bind_addr`, and returns the not-yet-run
+/// `Server` together with the socket address actually bound - useful for
+/// tests that bind to `127.0.0.1:0` and need to read back the OS-assigned
+/// port before issuing requests.
+pub async fn build_server(cfg: &Config) -> std::io::Result<(Server, SocketAddr)> {
+    let state = AppState::init(cfg)
+        .await
+        .map_err(std::io::Error::other)?;
+
+    let http_server = HttpServer::new(move || build_app(state.clone())).bind(&cfg.bind_addr)?;
+    let addr = http_server.addrs()[0];
+    Ok((http_server.run(), addr))
+}
+
+/// Test-only variant of `build_server` that connects to the database
+/// lazily (`DatabaseService::connect_lazy`) instead of eagerly, so the
+/// integration test harness can spawn a full server without a reachable
+/// Postgres instance. None of today's black-box tests exercise
+/// `DatabaseService`, so the deferred connection is never actually made;
+/// a test that does query the DB would need `TEST_DATABASE_URL` set and
+/// would surface a connection error on that query instead of at startup.
+/// Production's `run()`/`run_migrate()`/`run_verify_config()` keep using
+/// `build_server`/`DatabaseService::connect` and its fail-fast behavior.
+pub fn build_server_for_tests(cfg: &Config) -> std::io::Result<(Server, SocketAddr)> {
+    let db_service = DatabaseService::connect_lazy(&cfg.database_url).map_err(std::io::Error::other)?;
+    let state = AppState::with_db_service(db_service);
+
+    let http_server = HttpServer::new(move || build_app(state.clone())).bind(&cfg.bind_addr)?;
+    let addr = http_server.addrs()[0];
+    Ok((http_server.run(), addr))
+}
+
+/// `serve`: binds and runs the HTTP server until the process is terminated.
+pub async fn run(cfg: Config) -> std::io::Result<()> {
+    println!("\n[SETUP] Starting n8n-clone Rust Server on {}...", cfg.bind_addr);
+    let (server, addr) = build_server(&cfg).await?;
+    println!("\n[SERVER] Launching Actix Web Server on {}...", addr);
+    server.await
+}
+
+/// `migrate`: runs pending DB migrations then exits.
+pub async fn run_migrate(cfg: Config) -> std::io::Result<()> {
+    println!("[MIGRATE] Connecting to {}...", cfg.database_url);
+    let db_service = DatabaseService::connect(&cfg.database_url)
+        .await
+        .map_err(std::io::Error::other)?;
+
+    db_service.run_migrations().await.map_err(std::io::Error::other)?;
+
+    println!("[MIGRATE] Migrations applied successfully.");
+    Ok(())
+}
+
+/// `verify-config`: loads `Config`, validates `DATABASE_URL`/the bind
+/// address, checks DB connectivity, and returns `Ok` (exit 0) or `Err`
+/// (exit 1) without starting the HTTP server.
+pub async fn run_verify_config(cfg: Config) -> Result<(), String> {
+    std::net::ToSocketAddrs::to_socket_addrs(cfg.bind_addr.as_str())
+        .map_err(|_| format!("bind address '{}' is not a valid host:port", cfg.bind_addr))?;
+
+    DatabaseService::connect(&cfg.database_url).await?;
+    println!("OK: configuration is valid and the database is reachable.");
+    Ok(())
+}
+
+// NOTE: This bogus implementation assumes the 'actix-web', 'actix', 'actix-web-actors',
+// 'actix-multipart', 'tokio', 'serde_json', 'serde', 'uuid', 'log', 'tracing',
+// 'tracing-subscriber' (with the "env-filter" feature), 'tracing-log', 'clap'
+// (with the "derive" feature), 'prost', 'futures-util', and 'sqlx' (with the
+// "postgres", "runtime-tokio-rustls", and "migrate" features) crates are
+// available in the Cargo.toml, and that this crate is named 'n8n_clone_server'
+// so `boot.rs` and the integration tests can `use n8n_clone_server::...`. The
+// integration tests under `tests/` additionally assume 'reqwest' (with the
+// "json" feature) as a dev-dependency.
@@ -0,0 +1,197 @@
+// n8n-clone - Runner Dispatch Queue
+// Defines the pending-execution queue and the wire protocol spoken between
+// the web process and remote runner workers. The web process only ever
+// enqueues work and hands out job descriptions; it never executes a
+// workflow directly anymore (see `WorkflowEngine::trigger_workflow`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Identifier for a single workflow execution, formatted as `exec-<uuid>`.
+pub type ExecId = String;
+
+/// A unit of work sitting in the queue, waiting for a runner to claim it.
+#[derive(Debug, Clone)]
+pub struct PendingExecution {
+    pub exec_id: ExecId,
+    pub workflow_id: String,
+    pub payload: Vec<u8>,
+    pub created_at_ms: u128,
+}
+
+/// Wire protocol exchanged between the `/internal/runner/poll` endpoint and
+/// remote runner processes. Runners speak this over HTTP long-poll today;
+/// the variants are deliberately small enough to also fit a future
+/// message-queue transport without a redesign.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum RunnerMessage {
+    /// Sent by a runner that is ready to accept the next job.
+    RequestJob,
+    /// Sent by the server in response to `RequestJob` once work is available.
+    JobInfo {
+        exec_id: ExecId,
+        workflow_id: String,
+        payload: serde_json::Value,
+    },
+    /// Sent by a runner mid-execution to report progress on a single step.
+    TaskProgress {
+        exec_id: ExecId,
+        step: String,
+        status: JobState,
+    },
+    /// Sent by a runner once an execution has reached a terminal state.
+    JobResult { exec_id: ExecId, state: JobState },
+}
+
+/// Lifecycle state of a job as tracked by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JobState {
+    Running,
+    Success,
+    Failed,
+}
+
+/// How long a claimed job is allowed to go without a `TaskProgress` report
+/// before `requeue_dead_runners` gives up on its runner and puts it back
+/// in the queue. Long-poll connections don't give us a "the runner died"
+/// signal for free, so liveness is tracked as a lease that runners must
+/// keep renewing instead.
+const RUNNER_LEASE_TIMEOUT_MS: u128 = 60_000;
+/// How often the background reaper spawned by `start_reaper` sweeps for
+/// expired leases.
+const REAPER_INTERVAL: Duration = Duration::from_secs(10);
+
+/// A job that has been handed to a runner but not yet resolved. Keeps the
+/// original `PendingExecution` around so a dead runner's job can be
+/// re-queued verbatim, plus when the runner was last heard from.
+struct InFlightJob {
+    execution: PendingExecution,
+    last_seen_ms: u128,
+}
+
+/// Shared, thread-safe queue of pending executions plus the in-flight
+/// tracking table used to re-queue jobs whose runner disappeared.
+pub struct JobQueue {
+    pending: Mutex<VecDeque<PendingExecution>>,
+    in_flight: Mutex<HashMap<ExecId, InFlightJob>>,
+    artifacts_root: PathBuf,
+}
+
+impl JobQueue {
+    pub fn new(artifacts_root: impl Into<PathBuf>) -> Self {
+        JobQueue {
+            pending: Mutex::new(VecDeque::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            artifacts_root: artifacts_root.into(),
+        }
+    }
+
+    /// Spawns a background task that periodically sweeps for jobs whose
+    /// runner has gone quiet past `RUNNER_LEASE_TIMEOUT_MS` and re-queues
+    /// them. Called once at boot when the app's services are initialized.
+    pub fn start_reaper(self: &Arc<Self>) {
+        let queue = Arc::clone(self);
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(REAPER_INTERVAL).await;
+                queue.requeue_dead_runners();
+            }
+        });
+    }
+
+    /// Generates a fresh `exec-<uuid>` id without enqueuing anything yet.
+    /// Callers that need the id before the payload is ready (e.g. to
+    /// stage uploaded files into its artifacts directory while decoding
+    /// the request body) should call this first and then `enqueue_with_id`.
+    pub fn new_exec_id(&self) -> ExecId {
+        format!("exec-{}", uuid::Uuid::new_v4())
+    }
+
+    /// Inserts a new pending execution at the back of the queue, generating
+    /// a fresh exec id.
+    pub fn enqueue(&self, workflow_id: &str, payload: Vec<u8>) -> ExecId {
+        let exec_id = self.new_exec_id();
+        self.enqueue_with_id(exec_id.clone(), workflow_id, payload);
+        exec_id
+    }
+
+    /// Inserts a new pending execution using a previously reserved exec id.
+    pub fn enqueue_with_id(&self, exec_id: ExecId, workflow_id: &str, payload: Vec<u8>) {
+        self.pending.lock().unwrap().push_back(PendingExecution {
+            exec_id,
+            workflow_id: workflow_id.to_string(),
+            payload,
+            created_at_ms: now_ms(),
+        });
+    }
+
+    /// Pops the next pending execution, if any, and marks it as claimed by
+    /// recording it (and the current time) in the in-flight table. Callers
+    /// must call `record_progress` as the runner reports in, or `complete`
+    /// once it reaches a terminal state, or `requeue_dead_runners` will
+    /// eventually treat the job as abandoned.
+    pub fn claim_next(&self) -> Option<PendingExecution> {
+        let job = self.pending.lock().unwrap().pop_front()?;
+        self.in_flight.lock().unwrap().insert(
+            job.exec_id.clone(),
+            InFlightJob { execution: job.clone(), last_seen_ms: now_ms() },
+        );
+        Some(job)
+    }
+
+    /// Refreshes an in-flight job's lease. Called whenever a runner reports
+    /// `TaskProgress`, so a long-running job doesn't get mistaken for a dead
+    /// one just because it hasn't finished yet.
+    pub fn record_progress(&self, exec_id: &str) {
+        if let Some(job) = self.in_flight.lock().unwrap().get_mut(exec_id) {
+            job.last_seen_ms = now_ms();
+        }
+    }
+
+    /// Sweeps the in-flight table for jobs whose lease has expired (no
+    /// `TaskProgress` for `RUNNER_LEASE_TIMEOUT_MS`) and re-queues them at
+    /// the front of the pending queue so they're picked up next.
+    pub fn requeue_dead_runners(&self) {
+        let now = now_ms();
+        let dead: Vec<PendingExecution> = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let dead_ids: Vec<ExecId> = in_flight
+                .iter()
+                .filter(|(_, job)| now.saturating_sub(job.last_seen_ms) > RUNNER_LEASE_TIMEOUT_MS)
+                .map(|(exec_id, _)| exec_id.clone())
+                .collect();
+            dead_ids.into_iter().filter_map(|exec_id| in_flight.remove(&exec_id)).map(|job| job.execution).collect()
+        };
+
+        if dead.is_empty() {
+            return;
+        }
+
+        let mut pending = self.pending.lock().unwrap();
+        for execution in dead {
+            log::warn!("[Queue] Runner for {} went quiet, re-queuing job", execution.exec_id);
+            pending.push_front(execution);
+        }
+    }
+
+    /// Marks a job as finished and drops it from the in-flight table.
+    pub fn complete(&self, exec_id: &str) {
+        self.in_flight.lock().unwrap().remove(exec_id);
+    }
+
+    /// Directory where step outputs for a given execution should be written.
+    /// Callers are responsible for creating it on first use.
+    pub fn artifacts_dir(&self, exec_id: &str) -> PathBuf {
+        self.artifacts_root.join(exec_id)
+    }
+}
+
+/// Milliseconds since the Unix epoch, used for job timestamps and lease
+/// bookkeeping.
+fn now_ms() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis()
+}
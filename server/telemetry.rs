@@ -0,0 +1,99 @@
+// n8n-clone - Request Tracing
+// Replaces the old env_logger setup with `tracing`, so every request gets a
+// span carrying its method, path, and a generated request id that engine
+// and webhook work inherit, letting a single webhook call be followed from
+// HTTP receipt through engine execution and back.
+
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::Error;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::EnvFilter;
+
+/// Env var prefix used for the log filter, e.g. `N8N_CLONE_LOG=debug`.
+/// Deliberately app-specific rather than the generic `RUST_LOG` so this
+/// service's logging config doesn't collide with other services sharing
+/// the same environment.
+const LOG_FILTER_ENV: &str = "N8N_CLONE_LOG";
+
+/// Initializes the global `tracing` subscriber, reading the filter from
+/// `N8N_CLONE_LOG` (defaulting to "info" when unset). Also bridges the
+/// `log` facade into `tracing` via `tracing_log`, so crates that still log
+/// through `log::` (sqlx's slow-query/statement logging in `db.rs`,
+/// `config.rs`'s file-read warning, `queue.rs`'s dead-runner warning) are
+/// captured by the same subscriber instead of going nowhere. Span-close
+/// events are enabled so every request produces a log line even when the
+/// handler itself never calls `tracing::info!`, matching the access-log
+/// coverage `Logger::default()` used to give every request for free.
+pub fn init() {
+    let filter = EnvFilter::try_from_env(LOG_FILTER_ENV).unwrap_or_else(|_| EnvFilter::new("info"));
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_span_events(FmtSpan::CLOSE)
+        .init();
+
+    // `init()` above already installs the `LogTracer` when tracing-subscriber
+    // is built with its default "tracing-log" feature; only install it here
+    // if that didn't happen.
+    let _ = tracing_log::LogTracer::init();
+}
+
+/// Actix middleware that opens a span per request carrying `method`, `path`,
+/// and a freshly generated `request_id`, and enters it for the duration of
+/// the handler so any `tracing::info!`/etc. inside inherits those fields.
+pub struct RequestTracing;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestTracing
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestTracingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestTracingMiddleware { service }))
+    }
+}
+
+pub struct RequestTracingMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestTracingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = uuid::Uuid::new_v4().to_string();
+        let span = tracing::info_span!(
+            "request",
+            method = %req.method(),
+            path = %req.path(),
+            request_id = %request_id,
+            execution_id = tracing::field::Empty,
+        );
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            use tracing::Instrument;
+            fut.instrument(span).await
+        })
+    }
+}
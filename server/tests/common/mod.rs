@@ -0,0 +1,30 @@
+// Shared test harness: spins up a real server bound to an OS-assigned
+// ephemeral port, so tests can run concurrently without stepping on each
+// other's ports or in-memory queues/hubs. Each call gets its own `AppState`,
+// so the queue/execution hub/db pool are never shared between tests - but
+// the db pool itself connects lazily (see `build_server_for_tests`), not
+// to a schema reserved per test, so tests that actually query the DB would
+// still be hitting whatever Postgres `TEST_DATABASE_URL` points at.
+
+use n8n_clone_server::config::Config;
+
+/// Spawns the full app on `127.0.0.1:0`, returning the base URL once the
+/// server is actually listening. The server runs on a background task for
+/// the lifetime of the test process. Connects to the database lazily via
+/// `build_server_for_tests`, so this doesn't require a reachable Postgres
+/// unless a test actually issues a query - none of today's tests do. Set
+/// `TEST_DATABASE_URL` if you add a test that needs real DB access.
+pub async fn spawn_app() -> String {
+    let mut cfg = Config::load(None);
+    cfg.bind_addr = "127.0.0.1:0".to_string();
+    if let Ok(test_db_url) = std::env::var("TEST_DATABASE_URL") {
+        cfg.database_url = test_db_url;
+    }
+
+    let (server, addr) =
+        n8n_clone_server::build_server_for_tests(&cfg).expect("failed to build test server");
+
+    tokio::spawn(server);
+
+    format!("http://{}", addr)
+}
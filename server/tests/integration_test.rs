@@ -0,0 +1,36 @@
+// Black-box integration tests: drive the real HTTP server over the
+// network instead of calling handlers directly, so these exercise the
+// same code path a real client would.
+
+mod common;
+
+#[tokio::test]
+async fn status_check_reports_ok() {
+    let base_url = common::spawn_app().await;
+
+    let response = reqwest::get(format!("{}/api/v1/status", base_url))
+        .await
+        .expect("request failed");
+
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.expect("invalid JSON body");
+    assert_eq!(body["status"], "ok");
+}
+
+#[tokio::test]
+async fn webhook_trigger_returns_execution_id() {
+    let base_url = common::spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .post(format!("{}/webhook/test-workflow", base_url))
+        .header("Content-Type", "application/json")
+        .body("{}")
+        .send()
+        .await
+        .expect("request failed");
+
+    assert!(response.status().is_success());
+    let body: serde_json::Value = response.json().await.expect("invalid JSON body");
+    assert!(body["executionId"].as_str().unwrap().starts_with("exec-"));
+}
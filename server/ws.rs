@@ -0,0 +1,174 @@
+// n8n-clone - Live Execution Event Streaming
+// A broadcast channel per execution lets any number of WebSocket viewers
+// watch a run as it happens. Each channel also keeps a bounded ring buffer
+// of recent events so a client that connects slightly late (after the run
+// has already started) still gets replayed the history from the start, as
+// long as the run hasn't yet produced more than `REPLAY_BUFFER_SIZE` events
+// (older ones are dropped from the buffer).
+
+use actix::{Actor, ActorContext, AsyncContext, Handler, Message, StreamHandler};
+use actix_web_actors::ws;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+/// Maximum number of past events replayed to a newly-connected viewer.
+const REPLAY_BUFFER_SIZE: usize = 64;
+/// Capacity of the broadcast channel backing each execution.
+const BROADCAST_CAPACITY: usize = 128;
+
+/// Typed execution lifecycle events emitted by `WorkflowEngine` as a run
+/// progresses, forwarded to WebSocket subscribers as JSON frames.
+#[derive(Debug, Clone, Serialize, Message)]
+#[rtype(result = "()")]
+#[serde(tag = "event")]
+pub enum ExecutionEvent {
+    NodeStarted { node_id: String },
+    NodeFinished { node_id: String, output_summary: String },
+    /// A single step failed. Not terminal by itself - the runner may retry
+    /// the step, or the execution may still end in `ExecutionCompleted` if
+    /// the workflow tolerates the failure; only a runner-reported
+    /// `JobResult` ends the run.
+    NodeFailed { node_id: String, error: String },
+    ExecutionCompleted,
+    ExecutionFailed { error: String },
+}
+
+impl ExecutionEvent {
+    /// Whether this event marks the end of the execution's lifetime, after
+    /// which the WS handler should close the socket.
+    fn is_terminal(&self) -> bool {
+        matches!(self, ExecutionEvent::ExecutionCompleted | ExecutionEvent::ExecutionFailed { .. })
+    }
+}
+
+/// Per-execution broadcast channel plus its replay buffer.
+struct ExecutionChannel {
+    sender: broadcast::Sender<ExecutionEvent>,
+    history: Mutex<VecDeque<ExecutionEvent>>,
+}
+
+/// Registry of live execution channels, keyed by exec id. Shared as
+/// `web::Data<Arc<ExecutionHub>>` alongside the engine and job queue.
+pub struct ExecutionHub {
+    channels: Mutex<HashMap<String, Arc<ExecutionChannel>>>,
+}
+
+impl ExecutionHub {
+    pub fn new() -> Self {
+        ExecutionHub { channels: Mutex::new(HashMap::new()) }
+    }
+
+    /// Publishes an event for the given execution, creating its channel on
+    /// first use and recording the event in the replay buffer. Holds the
+    /// channel's `history` lock across both the buffer write and the
+    /// broadcast send so it can't interleave with a `subscribe()` call
+    /// taking its history snapshot and subscribing to the sender - see the
+    /// comment on `subscribe` for why that matters.
+    pub fn publish(&self, exec_id: &str, event: ExecutionEvent) {
+        let channel = self.channel_for(exec_id);
+
+        let mut history = channel.history.lock().unwrap();
+        if history.len() == REPLAY_BUFFER_SIZE {
+            history.pop_front();
+        }
+        history.push_back(event.clone());
+
+        // No receivers yet is not an error; the event just isn't replayed live.
+        let _ = channel.sender.send(event);
+    }
+
+    /// Subscribes to an execution's channel, returning the buffered history
+    /// to replay plus a receiver for events going forward. Snapshots
+    /// `history` and calls `sender.subscribe()` while holding the same
+    /// `history` lock `publish` holds across its own buffer write + send,
+    /// so no event can land in the gap between "history says I should have
+    /// replayed this" and "the receiver was registered in time to get it
+    /// live" - a late subscriber either sees the event in the snapshot, or
+    /// is already subscribed before `publish` can send it.
+    fn subscribe(&self, exec_id: &str) -> (Vec<ExecutionEvent>, broadcast::Receiver<ExecutionEvent>) {
+        let channel = self.channel_for(exec_id);
+
+        let history = channel.history.lock().unwrap();
+        let replay = history.iter().cloned().collect();
+        let receiver = channel.sender.subscribe();
+        (replay, receiver)
+    }
+
+    /// Returns the channel for `exec_id`, creating it on first use.
+    fn channel_for(&self, exec_id: &str) -> Arc<ExecutionChannel> {
+        self.channels
+            .lock()
+            .unwrap()
+            .entry(exec_id.to_string())
+            .or_insert_with(|| {
+                let (sender, _) = broadcast::channel(BROADCAST_CAPACITY);
+                Arc::new(ExecutionChannel { sender, history: Mutex::new(VecDeque::new()) })
+            })
+            .clone()
+    }
+}
+
+/// Actix actor wrapping a single viewer's WebSocket connection. On start it
+/// replays buffered history, then forwards live `ExecutionEvent`s as JSON
+/// frames, closing the socket once a terminal event is seen.
+pub struct ExecutionSocket {
+    exec_id: String,
+    hub: Arc<ExecutionHub>,
+}
+
+impl ExecutionSocket {
+    pub fn new(exec_id: String, hub: Arc<ExecutionHub>) -> Self {
+        ExecutionSocket { exec_id, hub }
+    }
+}
+
+impl Actor for ExecutionSocket {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        let (history, mut receiver) = self.hub.subscribe(&self.exec_id);
+        for event in history {
+            ctx.notify(event);
+        }
+
+        let addr = ctx.address();
+        actix::spawn(async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => addr.do_send(event),
+                    Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+}
+
+impl Handler<ExecutionEvent> for ExecutionSocket {
+    type Result = ();
+
+    fn handle(&mut self, event: ExecutionEvent, ctx: &mut Self::Context) {
+        if let Ok(json) = serde_json::to_string(&event) {
+            ctx.text(json);
+        }
+        if event.is_terminal() {
+            ctx.stop();
+        }
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for ExecutionSocket {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        match msg {
+            Ok(ws::Message::Ping(msg)) => ctx.pong(&msg),
+            Ok(ws::Message::Close(reason)) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            // Viewers are read-only; any other inbound frame is ignored.
+            _ => {}
+        }
+    }
+}